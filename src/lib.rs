@@ -51,6 +51,41 @@ pub use paste as _paste;
 /// }
 /// ```
 ///
+/// There's also a `tglog!` macro (and its `<prefix>log` counterpart)
+/// generated alongside the fixed-level ones, which forwards to
+/// `log::log!` and lets you pick the level at call time:
+///
+/// ```edition2018
+/// use targeted_log::targeted_log;
+///
+/// targeted_log!("potato-{}-{}");
+/// fn do_some_work() {
+///     tglog!(log::Level::Warn, "Picked at runtime");
+///     tglog!(@ 1, 2; log::Level::Warn, "Picked at runtime, with a formatted target");
+/// }
+/// ```
+///
+/// The generated macros also forward structured key-value fields
+/// (as supported by `log` 0.4's `kv` feature) ahead of the message,
+/// same as calling `log::info!` directly:
+///
+/// Note that this, like `log::info!(key = value; "...")` itself,
+/// requires the `kv` Cargo feature of `log` to be enabled by the
+/// final binary; without it, `log`'s own macros reject the `key =
+/// value` syntax, so this example is marked `ignore` rather than
+/// compiled as a doctest (this crate doesn't control whether its
+/// dependents enable `log/kv`):
+///
+/// ```ignore
+/// use targeted_log::targeted_log;
+///
+/// targeted_log!("potato-{}");
+/// fn do_some_work() {
+///     tginfo!(id = 1, attempt = 2; "Hey! I'm doing some work");
+///     tginfo!(@ 1; id = 1; "Hey! I'm doing some work, with a formatted target too");
+/// }
+/// ```
+///
 /// Of course, calling this macro more than once per module will cause
 /// a conflict between names.  For that cases, when we want to use
 /// multiple logging targets within one module, we can specify the
@@ -79,28 +114,118 @@ macro_rules! targeted_log {
 	&format!($tgt, $($arg_target),+)
     };
 
+    // Maps a fixed-level macro name to its `log::Level` constant, so
+    // the `@internal` branch can pre-check the level before formatting
+    // a dynamic target and pass it to `log_enabled!` afterwards.
+    (@level_of error) => { log::Level::Error };
+    (@level_of warn) => { log::Level::Warn };
+    (@level_of info) => { log::Level::Info };
+    (@level_of debug) => { log::Level::Debug };
+    (@level_of trace) => { log::Level::Trace };
+
     // This branch is for internal use only.  Generates a macro with
     // name `$implname` that calls the macro `$fun` with the target
     // `$tgt`. The $d parameter is a workaround for nesting macros,
     // and define repetitions in binding patterns correctly. It MUST
     // be set to $. See
     // https://github.com/rust-lang/rust/issues/35853#issuecomment-415993963
+    // The four arms below are ordered most-specific-first (target
+    // override with kv, target override alone, kv alone, plain), so
+    // invocations with any combination of the two optional groups
+    // parse without a local-ambiguity error. The two arms with a
+    // target override pre-check the level against both the
+    // compile-time `log::STATIC_MAX_LEVEL` and the crate-wide
+    // `log::max_level()` before building the `format!`'d target, so a
+    // statically/globally disabled level never pays for the
+    // allocation. That pre-check is deliberately against those two
+    // limits rather than `log_enabled!`, and deliberately
+    // before formatting: `log_enabled!` also consults the active
+    // `Log` impl's `enabled()`, which may filter by the *formatted*
+    // target (e.g. per-request routing), so it's only evaluated once
+    // the real target string is in hand, never against the raw
+    // template.
     (@internal $tgt:expr, $fun:ident, $implname:ident, $d: tt) => {
 	#[allow(unused)]
 	macro_rules! $implname {
-	    ($d(@ $d($d arg_target:expr),+;)? $d($d arg:expr),+) => {
-		log::$fun!(target: $crate::targeted_log!(@fmt_tgt $tgt $d(, $d($d arg_target),+)?), $d($d arg),+);
+	    (@ $d($d arg_target:expr),+; $d($d key:ident = $d val:expr),+; $d($d arg:expr),+) => {
+		if $crate::targeted_log!(@level_of $fun) <= log::STATIC_MAX_LEVEL
+		    && $crate::targeted_log!(@level_of $fun) <= log::max_level()
+		{
+		    let target = format!($tgt, $d($d arg_target),+);
+		    if log::log_enabled!(target: &target, $crate::targeted_log!(@level_of $fun)) {
+			log::$fun!(target: &target, $d($d key = $d val),+; $d($d arg),+);
+		    }
+		}
+	    };
+	    (@ $d($d arg_target:expr),+; $d($d arg:expr),+) => {
+		if $crate::targeted_log!(@level_of $fun) <= log::STATIC_MAX_LEVEL
+		    && $crate::targeted_log!(@level_of $fun) <= log::max_level()
+		{
+		    let target = format!($tgt, $d($d arg_target),+);
+		    if log::log_enabled!(target: &target, $crate::targeted_log!(@level_of $fun)) {
+			log::$fun!(target: &target, $d($d arg),+);
+		    }
+		}
+	    };
+	    ($d($d key:ident = $d val:expr),+; $d($d arg:expr),+) => {
+		log::$fun!(target: $crate::targeted_log!(@fmt_tgt $tgt), $d($d key = $d val),+; $d($d arg),+);
+	    };
+	    ($d($d arg:expr),+) => {
+		log::$fun!(target: $crate::targeted_log!(@fmt_tgt $tgt), $d($d arg),+);
+	    };
+	}
+    };
+
+    // Like `@internal`, but for `log::log!`, whose signature takes a
+    // dynamic `log::Level` expression before the format arguments.
+    // Same most-specific-first ordering and level pre-check
+    // as `@internal`; `$level` is bound to a local once so it's only
+    // evaluated a single time despite being used in the pre-check,
+    // the `log_enabled!` check and the actual call.
+    (@internal_log $tgt:expr, $implname:ident, $d: tt) => {
+	#[allow(unused)]
+	macro_rules! $implname {
+	    (@ $d($d arg_target:expr),+; $d level:expr, $d($d key:ident = $d val:expr),+; $d($d arg:expr),+) => {
+		{
+		    let level = $d level;
+		    if level <= log::STATIC_MAX_LEVEL && level <= log::max_level() {
+			let target = format!($tgt, $d($d arg_target),+);
+			if log::log_enabled!(target: &target, level) {
+			    log::log!(target: &target, level, $d($d key = $d val),+; $d($d arg),+);
+			}
+		    }
+		}
+	    };
+	    (@ $d($d arg_target:expr),+; $d level:expr, $d($d arg:expr),+) => {
+		{
+		    let level = $d level;
+		    if level <= log::STATIC_MAX_LEVEL && level <= log::max_level() {
+			let target = format!($tgt, $d($d arg_target),+);
+			if log::log_enabled!(target: &target, level) {
+			    log::log!(target: &target, level, $d($d arg),+);
+			}
+		    }
+		}
+	    };
+	    ($d level:expr, $d($d key:ident = $d val:expr),+; $d($d arg:expr),+) => {
+		log::log!(target: $crate::targeted_log!(@fmt_tgt $tgt), $d level, $d($d key = $d val),+; $d($d arg),+);
+	    };
+	    ($d level:expr, $d($d arg:expr),+) => {
+		log::log!(target: $crate::targeted_log!(@fmt_tgt $tgt), $d level, $d($d arg),+);
 	    };
 	}
     };
 
     // This branch will generate the macros for the given logging functions
-    // `$impl` prefixed with `$prefix` and the specified target `$tgt`
+    // `$impl` prefixed with `$prefix` and the specified target `$tgt`,
+    // plus a `log` variant that forwards to `log::log!` and accepts a
+    // runtime `log::Level` as its first argument.
     ($tgt:expr, $prefix:ident, [$($impl:ident),*]) => {
 	$crate::_paste::paste! {
 	    $(
 		$crate::targeted_log!(@internal $tgt, $impl, [<$prefix $impl>], $);
 	    )*
+	    $crate::targeted_log!(@internal_log $tgt, [<$prefix log>], $);
 	}
 
     };
@@ -117,3 +242,138 @@ macro_rules! targeted_log {
 	$crate::targeted_log!($tgt, tg);
     }
 }
+
+/// Like [`targeted_log!`], but the generated macros are re-exported
+/// with `$vis use` right after they're defined, instead of staying
+/// textually scoped to the invocation site. This lets a target be
+/// declared once in, say, `crate::logging`, and imported elsewhere
+/// with a normal path-based `use`, matching the Rust 2018 macro
+/// import model:
+///
+/// ```edition2018
+/// mod logging {
+///     targeted_log::targeted_log_export!(pub(crate), "app");
+/// }
+///
+/// use logging::tginfo;
+/// fn do_some_work() {
+///     tginfo!("Hey! I'm doing some work, from anywhere in the crate");
+/// }
+/// ```
+///
+/// As with a normal `use`, the invocation must sit directly inside a
+/// `mod` (not an arbitrary block) for the re-export to resolve.
+/// `$vis` must always be followed by a comma (a `vis` fragment can't
+/// be glued directly to the `$tgt:expr` that follows it), but can
+/// itself be left empty for a re-export private to the enclosing
+/// module, same as a bare `use` item: `targeted_log_export!(, "app")`. Takes the same
+/// `$tgt`, `$prefix` and function-list arguments as [`targeted_log!`].
+#[macro_export]
+macro_rules! targeted_log_export {
+    // Internal branches, analogous to `targeted_log!`'s `@internal`
+    // and `@internal_log`: delegate the actual macro definition to
+    // `targeted_log!` (the single source of truth for the wrapper
+    // bodies) and just add the `$vis use` re-export on top.
+    (@internal $tgt:expr, $fun:ident, $implname:ident, $vis:vis, $d: tt) => {
+	$crate::targeted_log!(@internal $tgt, $fun, $implname, $d);
+	$vis use $implname;
+    };
+
+    (@internal_log $tgt:expr, $implname:ident, $vis:vis, $d: tt) => {
+	$crate::targeted_log!(@internal_log $tgt, $implname, $d);
+	$vis use $implname;
+    };
+
+    // This branch will generate the macros for the given logging functions
+    // `$impl` prefixed with `$prefix` and the specified target `$tgt`,
+    // re-exported with visibility `$vis`. Note the comma right after
+    // `$vis:vis`: a `vis` fragment may only be followed by `,`, an
+    // `ident`, or a type, so it can't be glued directly to `$tgt:expr`.
+    ($vis:vis, $tgt:expr, $prefix:ident, [$($impl:ident),*]) => {
+	$crate::_paste::paste! {
+	    $(
+		$crate::targeted_log_export!(@internal $tgt, $impl, [<$prefix $impl>], $vis, $);
+	    )*
+	    $crate::targeted_log_export!(@internal_log $tgt, [<$prefix log>], $vis, $);
+	}
+    };
+
+    // This branch will generate the macros prefixed with `$prefix` and the
+    // specified target `$tgt`, re-exported with visibility `$vis`.
+    ($vis:vis, $tgt:expr, $prefix:ident) => {
+	$crate::targeted_log_export!($vis, $tgt, $prefix, [error, warn, info, debug, trace]);
+    };
+
+    // This branch will generate the macros prefixed with "tg" and the
+    // specified target `$tgt`, re-exported with visibility `$vis`.
+    ($vis:vis, $tgt:expr) => {
+	$crate::targeted_log_export!($vis, $tgt, tg);
+    }
+}
+
+/// Applies the target `$tgt` to the plain logging macros (`info!`,
+/// `warn!`...) for the extent of `$body`, instead of defining a whole
+/// new `tg`-prefixed macro set. This is handy for a one-off region
+/// where declaring and naming a new target via [`targeted_log!`]
+/// would be overkill:
+///
+/// ```edition2018
+/// use targeted_log::tg_scope;
+///
+/// fn do_some_work() {
+///     tg_scope!("db-tx" => {
+///         info!("Hey! I'm doing some work");
+///         warn!("Warning! This is a warning!");
+///     });
+/// }
+/// ```
+///
+/// Internally, this re-declares `info!`/`warn!`/... as local
+/// `macro_rules!` within `$body` that inject `target: $tgt`, so
+/// existing `info!`-style calls inside the block transparently pick
+/// up the scoped target and go back to their usual meaning once the
+/// block ends.
+///
+/// Important: this only works if the surrounding scope has *not*
+/// brought `info!`/`warn!`/... into scope via `use log::{info,
+/// warn, ...};`, as in the example above. Rust's macro resolution
+/// treats a textually-scoped `macro_rules!` and a path-imported
+/// macro of the same name as ambiguous (`error[E0659]`) rather than
+/// letting the local one shadow the import, so a prior `use
+/// log::info;` anywhere in the enclosing module will make every bare
+/// `info!(...)` inside the block fail to compile, even though that
+/// same call would work fine outside of `tg_scope!`. There's no way
+/// around this short of not bringing `log`'s macros into scope by
+/// name in files that use `tg_scope!` (call `log::info!(...)` by its
+/// full path elsewhere in those files instead) — `tg_scope!` itself
+/// cannot intercept an already fully-qualified `log::info!(...)`
+/// call, since that bypasses macro-name resolution entirely.
+#[macro_export]
+macro_rules! tg_scope {
+    // This branch is for internal use only. Shadows each `$impl` in
+    // `$($impl),*` with a local macro injecting the target `$tgt`,
+    // for the remainder of the scope it's invoked in, by delegating
+    // to `targeted_log!`'s `@internal` branch (the single source of
+    // truth for the wrapper body) with the macro's own name as its
+    // implementation name. See that branch for the `$d` workaround.
+    (@shadow $tgt:expr, [$($impl:ident),*], $d: tt) => {
+	$(
+	    $crate::targeted_log!(@internal $tgt, $impl, $impl, $d);
+	)*
+    };
+
+    // This branch scopes the given logging functions `$impl` to the
+    // target `$tgt` for the extent of `$body`.
+    ($tgt:expr, [$($impl:ident),*] => $body:block) => {
+	{
+	    $crate::tg_scope!(@shadow $tgt, [$($impl),*], $);
+	    $body
+	}
+    };
+
+    // This branch scopes the default set of logging functions to the
+    // target `$tgt` for the extent of `$body`.
+    ($tgt:expr => $body:block) => {
+	$crate::tg_scope!($tgt, [error, warn, info, debug, trace] => $body);
+    };
+}